@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -52,21 +54,274 @@ pub fn find_intersection(
     Some(Point::new(x, y))
 }
 
+// A crossing with enough context for musical mapping: the point, the index of
+// the crossing edge in each polygon, and the parametric positions along each
+// edge. `ua`/`ub` let the JS side map position-along-an-edge to pitch/velocity
+// deterministically.
+#[wasm_bindgen]
+pub struct Intersection {
+    x: f64,
+    y: f64,
+    i: usize,
+    j: usize,
+    ua: f64,
+    ub: f64,
+}
+
+#[wasm_bindgen]
+impl Intersection {
+    #[wasm_bindgen(getter)]
+    pub fn x(&self) -> f64 {
+        self.x
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn i(&self) -> usize {
+        self.i
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn j(&self) -> usize {
+        self.j
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn ua(&self) -> f64 {
+        self.ua
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn ub(&self) -> f64 {
+        self.ub
+    }
+}
+
+// Same segment math as `find_intersection`, but keeps the parametric values
+// instead of discarding them.
+fn intersection_params(p1: &Point, p2: &Point, p3: &Point, p4: &Point) -> Option<(f64, f64)> {
+    let denominator = (p4.y - p3.y) * (p2.x - p1.x) - (p4.x - p3.x) * (p2.y - p1.y);
+    if denominator.abs() < 1e-10 {
+        return None;
+    }
+    let ua = ((p4.x - p3.x) * (p1.y - p3.y) - (p4.y - p3.y) * (p1.x - p3.x)) / denominator;
+    let ub = ((p2.x - p1.x) * (p1.y - p3.y) - (p2.y - p1.y) * (p1.x - p3.x)) / denominator;
+    if !(0.0..=1.0).contains(&ua) || !(0.0..=1.0).contains(&ub) {
+        return None;
+    }
+    Some((ua, ub))
+}
+
+// Structured variant of `find_all_intersections`: reports which edges crossed
+// and where along each, keeping the same AABB broad-phase as the flat scan.
+#[wasm_bindgen]
+pub fn find_all_intersections_meta(
+    vertices1: &[f64],
+    vertices2: &[f64],
+) -> Vec<Intersection> {
+    let mut intersections = Vec::new();
+    if vertices1.is_empty() || vertices2.is_empty() {
+        return intersections;
+    }
+    if boxes_disjoint(&polygon_bbox(vertices1), &polygon_bbox(vertices2)) {
+        return intersections;
+    }
+
+    let edges1 = edge_boxes(vertices1);
+    let edges2 = edge_boxes(vertices2);
+
+    for i in (0..vertices1.len()).step_by(2) {
+        let box1 = &edges1[i / 2];
+        for j in (0..vertices2.len()).step_by(2) {
+            let box2 = &edges2[j / 2];
+            if boxes_disjoint(box1, box2) {
+                continue;
+            }
+
+            let p1 = Point::new(vertices1[i], vertices1[i + 1]);
+            let p2 = Point::new(vertices1[(i + 2) % vertices1.len()],
+                                vertices1[(i + 3) % vertices1.len()]);
+            let p3 = Point::new(vertices2[j], vertices2[j + 1]);
+            let p4 = Point::new(vertices2[(j + 2) % vertices2.len()],
+                                vertices2[(j + 3) % vertices2.len()]);
+
+            if let Some((ua, ub)) = intersection_params(&p1, &p2, &p3, &p4) {
+                intersections.push(Intersection {
+                    x: p1.x + ua * (p2.x - p1.x),
+                    y: p1.y + ua * (p2.y - p1.y),
+                    i: i / 2,
+                    j: j / 2,
+                    ua,
+                    ub,
+                });
+            }
+        }
+    }
+
+    intersections
+}
+
+// Above this many combined vertices the brute-force O(n·m) scan gets replaced
+// by the x-sorted active-interval scan. Small shapes stay on the straight loop,
+// which has no sort or active-list bookkeeping overhead.
+const ACTIVE_SCAN_THRESHOLD: usize = 64;
+
+// Outcome of the robust segment predicate. Unlike `find_intersection`, which
+// collapses everything non-crossing to `None`, this keeps the collinear case
+// so callers can tell a sustained overlap (two edges sliding along each other)
+// from a transient touch — useful for sustained vs. transient note triggering.
+pub enum SegmentIntersection {
+    Proper(Point),
+    Collinear { start: Point, end: Point },
+    None,
+}
+
+// Signed area of triangle (a, b, c): positive for a counter-clockwise turn,
+// negative for clockwise, zero when the three points are collinear.
+fn orient(a: &Point, b: &Point, c: &Point) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+// True when `p` lies within the bounding box of segment `a`-`b`. Combined with
+// a zero orientation this confirms on-segment containment.
+fn on_segment_box(a: &Point, b: &Point, p: &Point) -> bool {
+    p.x >= a.x.min(b.x) && p.x <= a.x.max(b.x) && p.y >= a.y.min(b.y) && p.y <= a.y.max(b.y)
+}
+
+// Orientation-based segment intersection test. A proper crossing exists when
+// each segment straddles the other's supporting line (opposite-signed
+// orientation pairs). Zero orientations fall through to an explicit collinear
+// overlap check reported as the shared subsegment.
+pub fn robust_intersection(
+    p1: &Point,
+    p2: &Point,
+    p3: &Point,
+    p4: &Point,
+) -> SegmentIntersection {
+    let d1 = orient(p3, p4, p1);
+    let d2 = orient(p3, p4, p2);
+    let d3 = orient(p1, p2, p3);
+    let d4 = orient(p1, p2, p4);
+
+    // Proper crossing: both pairs have strictly opposite signs.
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        let denominator = (p4.y - p3.y) * (p2.x - p1.x) - (p4.x - p3.x) * (p2.y - p1.y);
+        let ua = ((p4.x - p3.x) * (p1.y - p3.y) - (p4.y - p3.y) * (p1.x - p3.x)) / denominator;
+        let x = p1.x + ua * (p2.x - p1.x);
+        let y = p1.y + ua * (p2.y - p1.y);
+        return SegmentIntersection::Proper(Point::new(x, y));
+    }
+
+    // Collinear overlap: all four orientations vanish, so both segments lie on
+    // the same line. Report the portion they share, if any.
+    if d1 == 0.0 && d2 == 0.0 && d3 == 0.0 && d4 == 0.0 {
+        // Candidate endpoints of each segment that fall inside the other.
+        let mut pts: Vec<Point> = Vec::new();
+        for p in [p1, p2] {
+            if on_segment_box(p3, p4, p) {
+                pts.push(Point::new(p.x, p.y));
+            }
+        }
+        for p in [p3, p4] {
+            if on_segment_box(p1, p2, p) {
+                pts.push(Point::new(p.x, p.y));
+            }
+        }
+        if pts.len() >= 2 {
+            // The overlap runs between the two extreme shared points.
+            let mut start = Point::new(pts[0].x, pts[0].y);
+            let mut end = Point::new(pts[0].x, pts[0].y);
+            for p in &pts {
+                if (p.x, p.y) < (start.x, start.y) {
+                    start = Point::new(p.x, p.y);
+                }
+                if (p.x, p.y) > (end.x, end.y) {
+                    end = Point::new(p.x, p.y);
+                }
+            }
+            return SegmentIntersection::Collinear { start, end };
+        }
+    }
+
+    // Touching endpoints (one orientation zero and the point in the box) still
+    // count as a proper contact point.
+    if d1 == 0.0 && on_segment_box(p3, p4, p1) {
+        return SegmentIntersection::Proper(Point::new(p1.x, p1.y));
+    }
+    if d2 == 0.0 && on_segment_box(p3, p4, p2) {
+        return SegmentIntersection::Proper(Point::new(p2.x, p2.y));
+    }
+    if d3 == 0.0 && on_segment_box(p1, p2, p3) {
+        return SegmentIntersection::Proper(Point::new(p3.x, p3.y));
+    }
+    if d4 == 0.0 && on_segment_box(p1, p2, p4) {
+        return SegmentIntersection::Proper(Point::new(p4.x, p4.y));
+    }
+
+    SegmentIntersection::None
+}
+
 #[wasm_bindgen]
 pub fn find_all_intersections(
-    vertices1: &[f64], 
+    vertices1: &[f64],
+    vertices2: &[f64]
+) -> Vec<f64> {
+    // Broad-phase reject: if the polygons' overall boxes don't overlap they
+    // can't cross, which is the common case for geomusica's layers once they
+    // rotate apart.
+    if vertices1.is_empty() || vertices2.is_empty() {
+        return Vec::new();
+    }
+    let box1 = polygon_bbox(vertices1);
+    let box2 = polygon_bbox(vertices2);
+    if boxes_disjoint(&box1, &box2) {
+        return Vec::new();
+    }
+
+    // Dispatch to the active-interval scan once the shapes get large enough
+    // that the quadratic scan dominates an animation frame.
+    if (vertices1.len() + vertices2.len()) / 2 > ACTIVE_SCAN_THRESHOLD {
+        find_all_intersections_active(vertices1, vertices2)
+    } else {
+        find_all_intersections_brute(vertices1, vertices2)
+    }
+}
+
+// Original O(n·m) scan: test every edge of polygon 1 against every edge of
+// polygon 2. Still the fastest option for the low vertex counts that make up
+// most geomusica layers.
+fn find_all_intersections_brute(
+    vertices1: &[f64],
     vertices2: &[f64]
 ) -> Vec<f64> {
     let mut intersections = Vec::new();
 
+    // Precompute each edge's axis-aligned bounding box once so the inner loop
+    // can reject most pairs with four comparisons instead of a division.
+    let edges1 = edge_boxes(vertices1);
+    let edges2 = edge_boxes(vertices2);
+
     // Iterate through line segments of both polygons
     for i in (0..vertices1.len()).step_by(2) {
+        let box1 = &edges1[i / 2];
         for j in (0..vertices2.len()).step_by(2) {
+            let box2 = &edges2[j / 2];
+            // Skip pairs whose boxes are disjoint on either axis.
+            if boxes_disjoint(box1, box2) {
+                continue;
+            }
+
             let p1 = Point::new(vertices1[i], vertices1[i+1]);
-            let p2 = Point::new(vertices1[(i+2) % vertices1.len()], 
+            let p2 = Point::new(vertices1[(i+2) % vertices1.len()],
                                 vertices1[(i+3) % vertices1.len()]);
             let p3 = Point::new(vertices2[j], vertices2[j+1]);
-            let p4 = Point::new(vertices2[(j+2) % vertices2.len()], 
+            let p4 = Point::new(vertices2[(j+2) % vertices2.len()],
                                 vertices2[(j+3) % vertices2.len()]);
 
             if let Some(intersection) = find_intersection(&p1, &p2, &p3, &p4) {
@@ -78,3 +333,1090 @@ pub fn find_all_intersections(
 
     intersections
 }
+
+// Axis-aligned bounding box as (min_x, min_y, max_x, max_y).
+type Bbox = (f64, f64, f64, f64);
+
+// Overall bounding box of a flat vertex array.
+fn polygon_bbox(vertices: &[f64]) -> Bbox {
+    let mut min_x = vertices[0];
+    let mut min_y = vertices[1];
+    let mut max_x = vertices[0];
+    let mut max_y = vertices[1];
+    for p in vertices.chunks_exact(2) {
+        min_x = min_x.min(p[0]);
+        min_y = min_y.min(p[1]);
+        max_x = max_x.max(p[0]);
+        max_y = max_y.max(p[1]);
+    }
+    (min_x, min_y, max_x, max_y)
+}
+
+// Bounding box of every edge, wrapping the last vertex back to the first.
+fn edge_boxes(vertices: &[f64]) -> Vec<Bbox> {
+    let n = vertices.len();
+    let mut boxes = Vec::with_capacity(n / 2);
+    for i in (0..n).step_by(2) {
+        let (ax, ay) = (vertices[i], vertices[i + 1]);
+        let (bx, by) = (vertices[(i + 2) % n], vertices[(i + 3) % n]);
+        boxes.push((ax.min(bx), ay.min(by), ax.max(bx), ay.max(by)));
+    }
+    boxes
+}
+
+// True when two boxes share no overlap on at least one axis.
+fn boxes_disjoint(a: &Bbox, b: &Bbox) -> bool {
+    a.2 < b.0 || b.2 < a.0 || a.3 < b.1 || b.3 < a.1
+}
+
+// A single polygon edge, endpoints ordered left-to-right by x (ties broken by
+// y). `poly` records which input polygon it came from so the scan emits only
+// the cross-polygon crossings that `find_all_intersections` cares about.
+#[derive(Clone, Copy)]
+struct ActiveSegment {
+    ax: f64,
+    ay: f64,
+    bx: f64,
+    by: f64,
+    poly: u8,
+}
+
+impl ActiveSegment {
+    fn new(x1: f64, y1: f64, x2: f64, y2: f64, poly: u8) -> ActiveSegment {
+        if (x1, y1) <= (x2, y2) {
+            ActiveSegment { ax: x1, ay: y1, bx: x2, by: y2, poly }
+        } else {
+            ActiveSegment { ax: x2, ay: y2, bx: x1, by: y1, poly }
+        }
+    }
+
+    // Crossing point with another segment, reusing the shared predicate.
+    fn cross(&self, other: &ActiveSegment) -> Option<Point> {
+        find_intersection(
+            &Point::new(self.ax, self.ay),
+            &Point::new(self.bx, self.by),
+            &Point::new(other.ax, other.ay),
+            &Point::new(other.bx, other.by),
+        )
+    }
+}
+
+// Build the left/right edge list for one polygon, tagging each edge with its
+// source so cross-polygon filtering is cheap later.
+fn collect_segments(vertices: &[f64], poly: u8, out: &mut Vec<ActiveSegment>) {
+    let n = vertices.len();
+    for i in (0..n).step_by(2) {
+        out.push(ActiveSegment::new(
+            vertices[i],
+            vertices[i + 1],
+            vertices[(i + 2) % n],
+            vertices[(i + 3) % n],
+            poly,
+        ));
+    }
+}
+
+// x-sorted active-interval intersection finder. Segments are processed
+// left-to-right by their left endpoint against an "active" list of segments
+// whose x-extent still spans the current x. Any crossing pair must overlap in
+// x, so both members are active at the moment the later-starting one is
+// inserted — testing each new segment against the active list there catches
+// every crossing exactly once, matching the brute scan.
+//
+// This is an x-band filter, NOT the Bentley-Ottmann sweepline: there is no
+// y-ordered sweep status and no neighbor-only tests, so a new segment is tested
+// against every still-active segment. Cost is O(n log n) for the sort plus
+// O(n·a) for an average active size a — a big win when shapes spread apart, but
+// it degrades to O(n²) when many edges share an x-extent (tall/dense shapes).
+#[wasm_bindgen]
+pub fn find_all_intersections_active(
+    vertices1: &[f64],
+    vertices2: &[f64],
+) -> Vec<f64> {
+    let mut segments = Vec::new();
+    collect_segments(vertices1, 0, &mut segments);
+    collect_segments(vertices2, 1, &mut segments);
+
+    // Emit only the cross-polygon crossings this entrypoint cares about.
+    active_interval_crossings(&segments, &|a, b| {
+        if segments[a].poly != segments[b].poly {
+            segments[a].cross(&segments[b])
+        } else {
+            None
+        }
+    })
+}
+
+// Shared active-interval scan over a prepared segment list. `crossing` decides,
+// for a candidate pair, whether its crossing belongs in the output and how the
+// point is computed: the two-shape entrypoint filters cross-polygon pairs and
+// uses the `find_intersection` parametric test, while the self-intersection
+// path filters non-adjacent pairs and uses the robust orientation predicate so
+// both of its size branches agree. Returns a flat [x, y, ...] array.
+fn active_interval_crossings(segments: &[ActiveSegment], crossing: &dyn Fn(usize, usize) -> Option<Point>) -> Vec<f64> {
+    // Visit segments in left-endpoint x order.
+    let mut order: Vec<usize> = (0..segments.len()).collect();
+    order.sort_by(|&a, &b| {
+        segments[a]
+            .ax
+            .partial_cmp(&segments[b].ax)
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut active: Vec<usize> = Vec::new();
+    let mut intersections = Vec::new();
+    for &idx in &order {
+        let ax = segments[idx].ax;
+        // Retire segments whose right end is behind the sweep; they can no
+        // longer cross anything to the right.
+        active.retain(|&s| segments[s].bx >= ax);
+        // `crossing` folds the emit filter and the predicate together, so each
+        // entrypoint chooses both which pairs count and how their crossing is
+        // computed.
+        for &other in &active {
+            if let Some(p) = crossing(idx, other) {
+                intersections.push(p.x);
+                intersections.push(p.y);
+            }
+        }
+        active.push(idx);
+    }
+
+    intersections
+}
+
+// ---------------------------------------------------------------------------
+// Polygon boolean operations (Greiner-Hormann clipping)
+// ---------------------------------------------------------------------------
+
+// Which boolean of the two polygons to trace out.
+#[derive(Clone, Copy, PartialEq)]
+enum BoolOp {
+    Intersection,
+    Union,
+    Difference,
+}
+
+// Result of a clip: all output rings packed into one flat coordinate array,
+// with `offsets` giving the vertex-count start of each contour so callers can
+// split the (possibly several) output rings apart.
+#[wasm_bindgen]
+pub struct ClipResult {
+    coords: Vec<f64>,
+    offsets: Vec<usize>,
+}
+
+#[wasm_bindgen]
+impl ClipResult {
+    // Flat [x, y, x, y, ...] of every output ring, concatenated.
+    #[wasm_bindgen(getter)]
+    pub fn coords(&self) -> Vec<f64> {
+        self.coords.clone()
+    }
+
+    // Start index (in coordinate pairs) of each contour within `coords`. A
+    // trailing entry equal to the total pair count lets callers treat it as a
+    // half-open range table.
+    #[wasm_bindgen(getter)]
+    pub fn offsets(&self) -> Vec<usize> {
+        self.offsets.clone()
+    }
+}
+
+// Arena-allocated node of a doubly-linked vertex ring. Intersections carry a
+// `neighbor` link to the matching node in the other polygon's ring plus the
+// entry/exit flag the trace walks on.
+struct ClipVertex {
+    x: f64,
+    y: f64,
+    next: usize,
+    prev: usize,
+    intersect: bool,
+    entry: bool,
+    visited: bool,
+    neighbor: usize,
+    alpha: f64,
+}
+
+// Ray-casting point-in-polygon test against a flat vertex array.
+fn point_in_polygon(x: f64, y: f64, vertices: &[f64]) -> bool {
+    let n = vertices.len();
+    let mut inside = false;
+    let mut j = n - 2;
+    for i in (0..n).step_by(2) {
+        let (xi, yi) = (vertices[i], vertices[i + 1]);
+        let (xj, yj) = (vertices[j], vertices[j + 1]);
+        if ((yi > y) != (yj > y)) && (x < (xj - xi) * (y - yi) / (yj - yi) + xi) {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+// Append one polygon's vertices as a circular doubly-linked ring in `arena`,
+// returning the index of the first node.
+fn build_ring(vertices: &[f64], arena: &mut Vec<ClipVertex>) -> usize {
+    let base = arena.len();
+    let count = vertices.len() / 2;
+    for k in 0..count {
+        arena.push(ClipVertex {
+            x: vertices[2 * k],
+            y: vertices[2 * k + 1],
+            next: base + (k + 1) % count,
+            prev: base + (k + count - 1) % count,
+            intersect: false,
+            entry: false,
+            visited: false,
+            neighbor: 0,
+            alpha: 0.0,
+        });
+    }
+    base
+}
+
+// Insert an intersection node into the edge that starts at `start`, keeping
+// the edge's intersection nodes ordered by their parametric `alpha`.
+fn insert_intersection(arena: &mut [ClipVertex], start: usize, node: usize) {
+    let mut cur = arena[start].next;
+    while arena[cur].intersect && arena[cur].alpha < arena[node].alpha {
+        cur = arena[cur].next;
+    }
+    let prev = arena[cur].prev;
+    arena[node].prev = prev;
+    arena[node].next = cur;
+    arena[prev].next = node;
+    arena[cur].prev = node;
+}
+
+// Append a whole ring to the packed coordinate array and record its end in the
+// offset table.
+fn push_ring(coords: &mut Vec<f64>, offsets: &mut Vec<usize>, ring: &[f64]) {
+    coords.extend_from_slice(ring);
+    offsets.push(coords.len() / 2);
+}
+
+// Core Greiner-Hormann clip shared by the three boolean entrypoints.
+fn clip_polygons(subject: &[f64], clip: &[f64], op: BoolOp) -> ClipResult {
+    let empty = ClipResult { coords: Vec::new(), offsets: vec![0] };
+    if subject.len() < 6 || clip.len() < 6 {
+        return empty;
+    }
+
+    let mut arena: Vec<ClipVertex> = Vec::new();
+    let subject_first = build_ring(subject, &mut arena);
+    let clip_first = build_ring(clip, &mut arena);
+
+    // Snapshots of the original ring nodes so the intersection insertion loop
+    // only walks real edges, not the nodes it is adding.
+    let subject_nodes: Vec<usize> = ring_nodes(&arena, subject_first);
+    let clip_nodes: Vec<usize> = ring_nodes(&arena, clip_first);
+
+    // Phase 1: find every crossing and splice it into both rings.
+    for &sa in &subject_nodes {
+        let sb = arena[sa].next;
+        for (k, &ca) in clip_nodes.iter().enumerate() {
+            let cb = clip_nodes[(k + 1) % clip_nodes.len()];
+            let p1 = Point::new(arena[sa].x, arena[sa].y);
+            let p2 = Point::new(arena[sb].x, arena[sb].y);
+            let p3 = Point::new(arena[ca].x, arena[ca].y);
+            let p4 = Point::new(arena[cb].x, arena[cb].y);
+            if let Some((ua, ub)) = intersection_params(&p1, &p2, &p3, &p4) {
+                let x = p1.x + ua * (p2.x - p1.x);
+                let y = p1.y + ua * (p2.y - p1.y);
+
+                let sn = arena.len();
+                arena.push(ClipVertex {
+                    x, y, next: 0, prev: 0, intersect: true, entry: false,
+                    visited: false, neighbor: 0, alpha: ua,
+                });
+                let cn = arena.len();
+                arena.push(ClipVertex {
+                    x, y, next: 0, prev: 0, intersect: true, entry: false,
+                    visited: false, neighbor: 0, alpha: ub,
+                });
+                arena[sn].neighbor = cn;
+                arena[cn].neighbor = sn;
+
+                insert_intersection(&mut arena, sa, sn);
+                insert_intersection(&mut arena, ca, cn);
+            }
+        }
+    }
+
+    // No crossings: the rings are either disjoint or one contains the other.
+    // Emit whole rings according to the boolean instead of dropping everything.
+    if !arena.iter().any(|v| v.intersect) {
+        let subject_in_clip = point_in_polygon(subject[0], subject[1], clip);
+        let clip_in_subject = point_in_polygon(clip[0], clip[1], subject);
+        let mut coords = Vec::new();
+        let mut offsets = vec![0];
+        match op {
+            BoolOp::Intersection => {
+                // Overlap is the inner ring when nested, nothing when disjoint.
+                if subject_in_clip {
+                    push_ring(&mut coords, &mut offsets, subject);
+                } else if clip_in_subject {
+                    push_ring(&mut coords, &mut offsets, clip);
+                }
+            }
+            BoolOp::Union => {
+                // Union is the outer ring when nested, both rings when disjoint.
+                if subject_in_clip {
+                    push_ring(&mut coords, &mut offsets, clip);
+                } else if clip_in_subject {
+                    push_ring(&mut coords, &mut offsets, subject);
+                } else {
+                    push_ring(&mut coords, &mut offsets, subject);
+                    push_ring(&mut coords, &mut offsets, clip);
+                }
+            }
+            BoolOp::Difference => {
+                if subject_in_clip {
+                    // Subject fully covered by the clip: nothing remains.
+                } else if clip_in_subject {
+                    // Clip sits inside the subject, leaving it as a hole.
+                    push_ring(&mut coords, &mut offsets, subject);
+                    push_ring(&mut coords, &mut offsets, clip);
+                } else {
+                    // Disjoint: the subject passes through unchanged.
+                    push_ring(&mut coords, &mut offsets, subject);
+                }
+            }
+        }
+        return ClipResult { coords, offsets };
+    }
+
+    // Phase 2: mark entry/exit on each ring relative to the other polygon,
+    // then flip flags to select the requested boolean.
+    let flip_subject = op == BoolOp::Union || op == BoolOp::Difference;
+    mark_entry_exit(&mut arena, subject_first, clip, flip_subject);
+    mark_entry_exit(&mut arena, clip_first, subject, op == BoolOp::Union);
+
+    // Phase 3: trace output contours, switching rings at each crossing.
+    let mut coords = Vec::new();
+    let mut offsets = vec![0];
+    let subject_inters: Vec<usize> =
+        ring_nodes(&arena, subject_first).into_iter().filter(|&i| arena[i].intersect).collect();
+
+    for &start in &subject_inters {
+        if arena[start].visited {
+            continue;
+        }
+        let mut current = start;
+        loop {
+            arena[current].visited = true;
+            let neighbor = arena[current].neighbor;
+            arena[neighbor].visited = true;
+            if arena[current].entry {
+                loop {
+                    current = arena[current].next;
+                    coords.push(arena[current].x);
+                    coords.push(arena[current].y);
+                    if arena[current].intersect {
+                        break;
+                    }
+                }
+            } else {
+                loop {
+                    current = arena[current].prev;
+                    coords.push(arena[current].x);
+                    coords.push(arena[current].y);
+                    if arena[current].intersect {
+                        break;
+                    }
+                }
+            }
+            current = arena[current].neighbor;
+            if current == start {
+                break;
+            }
+        }
+        offsets.push(coords.len() / 2);
+    }
+
+    ClipResult { coords, offsets }
+}
+
+// Collect the arena indices of one ring in link order.
+fn ring_nodes(arena: &[ClipVertex], first: usize) -> Vec<usize> {
+    let mut nodes = Vec::new();
+    let mut cur = first;
+    loop {
+        nodes.push(cur);
+        cur = arena[cur].next;
+        if cur == first {
+            break;
+        }
+    }
+    nodes
+}
+
+// Walk one ring and flag each intersection as an entry or exit into `other`,
+// optionally inverting the sense (used to turn intersection into union /
+// difference).
+fn mark_entry_exit(arena: &mut [ClipVertex], first: usize, other: &[f64], flip: bool) {
+    let mut inside = point_in_polygon(arena[first].x, arena[first].y, other);
+    let mut cur = first;
+    loop {
+        if arena[cur].intersect {
+            let mut entry = !inside;
+            if flip {
+                entry = !entry;
+            }
+            arena[cur].entry = entry;
+            inside = !inside;
+        }
+        cur = arena[cur].next;
+        if cur == first {
+            break;
+        }
+    }
+}
+
+// Intersection (overlap) region of two simple polygons.
+#[wasm_bindgen]
+pub fn polygon_intersection(subject: &[f64], clip: &[f64]) -> ClipResult {
+    clip_polygons(subject, clip, BoolOp::Intersection)
+}
+
+// Union (merged) region of two simple polygons.
+#[wasm_bindgen]
+pub fn polygon_union(subject: &[f64], clip: &[f64]) -> ClipResult {
+    clip_polygons(subject, clip, BoolOp::Union)
+}
+
+// Difference (subject minus clip) of two simple polygons.
+#[wasm_bindgen]
+pub fn polygon_difference(subject: &[f64], clip: &[f64]) -> ClipResult {
+    clip_polygons(subject, clip, BoolOp::Difference)
+}
+
+// Signed shoelace area of a single flat ring.
+fn ring_signed_area(ring: &[f64]) -> f64 {
+    let n = ring.len();
+    let mut area = 0.0;
+    let mut j = n - 2;
+    for i in (0..n).step_by(2) {
+        area += (ring[j] + ring[i]) * (ring[i + 1] - ring[j + 1]);
+        j = i;
+    }
+    area / 2.0
+}
+
+// Total signed area of the intersection region, so callers can drive amplitude
+// from overlap magnitude without re-splitting the contour table.
+#[wasm_bindgen]
+pub fn intersection_area(subject: &[f64], clip: &[f64]) -> f64 {
+    let result = clip_polygons(subject, clip, BoolOp::Intersection);
+    let mut area = 0.0;
+    for w in result.offsets.windows(2) {
+        let ring = &result.coords[w[0] * 2..w[1] * 2];
+        area += ring_signed_area(ring);
+    }
+    area
+}
+
+// ---------------------------------------------------------------------------
+// WKT (Well-Known Text) import/export
+// ---------------------------------------------------------------------------
+
+// Serialize a point as `POINT (x y)`.
+#[wasm_bindgen]
+pub fn point_to_wkt(point: &Point) -> String {
+    format!("POINT ({} {})", point.x, point.y)
+}
+
+// Parse `POLYGON ((x y, x y, ...))` into a flat vertex array, dropping a
+// repeated closing vertex so the result matches the open rings the rest of
+// this module works with. Returns an empty vec on malformed input.
+#[wasm_bindgen]
+pub fn parse_wkt_polygon(wkt: &str) -> Vec<f64> {
+    // Pull out the coordinate list between the inner parentheses.
+    let inner = match (wkt.find('('), wkt.rfind(')')) {
+        (Some(open), Some(close)) if close > open => &wkt[open + 1..close],
+        _ => return Vec::new(),
+    };
+    let inner = inner.trim().trim_start_matches('(').trim_end_matches(')');
+
+    let mut coords = Vec::new();
+    for pair in inner.split(',') {
+        let mut parts = pair.split_whitespace();
+        match (parts.next(), parts.next()) {
+            (Some(x), Some(y)) => match (x.parse::<f64>(), y.parse::<f64>()) {
+                (Ok(x), Ok(y)) => {
+                    coords.push(x);
+                    coords.push(y);
+                }
+                _ => return Vec::new(),
+            },
+            _ => return Vec::new(),
+        }
+    }
+
+    // Drop the repeated closing vertex if present.
+    let n = coords.len();
+    if n >= 4 && coords[0] == coords[n - 2] && coords[1] == coords[n - 1] {
+        coords.truncate(n - 2);
+    }
+
+    coords
+}
+
+// Serialize a flat vertex array as `POLYGON ((x y, ...))`, closing the ring by
+// repeating the first vertex as WKT expects.
+#[wasm_bindgen]
+pub fn polygon_to_wkt(vertices: &[f64]) -> String {
+    if vertices.len() < 6 {
+        return String::from("POLYGON EMPTY");
+    }
+
+    let mut points: Vec<String> = vertices
+        .chunks_exact(2)
+        .map(|p| format!("{} {}", p[0], p[1]))
+        .collect();
+    // Close the ring unless it is already closed.
+    let n = vertices.len();
+    if vertices[0] != vertices[n - 2] || vertices[1] != vertices[n - 1] {
+        points.push(format!("{} {}", vertices[0], vertices[1]));
+    }
+
+    format!("POLYGON (({}))", points.join(", "))
+}
+
+// ---------------------------------------------------------------------------
+// Polygon offset / stroke generator
+// ---------------------------------------------------------------------------
+
+// Unit vector of (dx, dy), or None for a degenerate (zero-length) segment.
+fn normalize(dx: f64, dy: f64) -> Option<(f64, f64)> {
+    let len = (dx * dx + dy * dy).sqrt();
+    if len < 1e-12 {
+        None
+    } else {
+        Some((dx / len, dy / len))
+    }
+}
+
+// Intersection of the infinite lines through the two offset edges, found by
+// stretching each edge far past its endpoints and reusing `find_intersection`.
+fn offset_edge_intersection(
+    a1: (f64, f64),
+    a2: (f64, f64),
+    b1: (f64, f64),
+    b2: (f64, f64),
+) -> Option<(f64, f64)> {
+    const EXT: f64 = 1.0e6;
+    let (adx, ady) = (a2.0 - a1.0, a2.1 - a1.1);
+    let (bdx, bdy) = (b2.0 - b1.0, b2.1 - b1.1);
+    let ea = Point::new(a1.0 - adx * EXT, a1.1 - ady * EXT);
+    let eb = Point::new(a2.0 + adx * EXT, a2.1 + ady * EXT);
+    let ec = Point::new(b1.0 - bdx * EXT, b1.1 - bdy * EXT);
+    let ed = Point::new(b2.0 + bdx * EXT, b2.1 + bdy * EXT);
+    find_intersection(&ea, &eb, &ec, &ed).map(|p| (p.x, p.y))
+}
+
+// Offset a closed polygon outline by signed distance `d`, producing a new
+// closed vertex array — positive `d` grows the outline outward, negative
+// shrinks it inward. Each corner joins the two incident offset edges: convex
+// turns miter to the intersection of the extended edges, reflex turns emit
+// both edge endpoints so thin features don't spike. A vertex next to a
+// zero-length edge emits no point. Offset edges that flip over because |d|
+// exceeds the local feature size are neither detected nor removed.
+#[wasm_bindgen]
+pub fn offset_polygon(vertices: &[f64], d: f64) -> Vec<f64> {
+    let count = vertices.len() / 2;
+    if count < 3 {
+        return Vec::new();
+    }
+
+    // Corner convexity is relative to the polygon's own winding, so the join
+    // choice is independent of the offset sign.
+    let winding = ring_signed_area(vertices).signum();
+    // The edge normals below point outward for a counter-clockwise ring; scale
+    // `d` by the winding so its sign means the same on a clockwise ring.
+    let d = d * winding;
+
+    let mut out = Vec::new();
+    for i in 0..count {
+        let prev = ((i + count - 1) % count) * 2;
+        let cur = i * 2;
+        let next = ((i + 1) % count) * 2;
+
+        let (px, py) = (vertices[prev], vertices[prev + 1]);
+        let (cx, cy) = (vertices[cur], vertices[cur + 1]);
+        let (nx, ny) = (vertices[next], vertices[next + 1]);
+
+        // Unit tangents of the two incident edges; skip collapsed segments.
+        let (ax, ay) = match normalize(cx - px, cy - py) {
+            Some(v) => v,
+            None => continue,
+        };
+        let (bx, by) = match normalize(nx - cx, ny - cy) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        // Normals are the tangents rotated 90°.
+        let (nax, nay) = (ay, -ax);
+        let (nbx, nby) = (by, -bx);
+
+        // The two edges shifted by `d` along their normals.
+        let a1 = (px + nax * d, py + nay * d);
+        let a2 = (cx + nax * d, cy + nay * d);
+        let b1 = (cx + nbx * d, cy + nby * d);
+        let b2 = (nx + nbx * d, ny + nby * d);
+
+        // Cross product of the edge directions against the winding tells a
+        // genuinely-convex corner from a reflex one, regardless of whether the
+        // offset runs outward or inward.
+        let cross = ax * by - ay * bx;
+        let convex = cross * winding > 0.0;
+
+        if convex {
+            // Convex corners join at the intersection of the two offset edges.
+            // When the edges diverge (outward offset) this extends them to fill
+            // the gap; when they overlap (inward offset) it trims them back —
+            // either way the intersection is the correct miter point.
+            match offset_edge_intersection(a1, a2, b1, b2) {
+                Some(p) => {
+                    out.push(p.0);
+                    out.push(p.1);
+                }
+                // The extended edges do not meet (near-parallel): fall back
+                // to the end of the first offset edge.
+                None => {
+                    out.push(a2.0);
+                    out.push(a2.1);
+                }
+            }
+        } else {
+            // Reflex corner: emit both offset endpoints to avoid a spike.
+            out.push(a2.0);
+            out.push(a2.1);
+            out.push(b1.0);
+            out.push(b1.1);
+        }
+    }
+
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Single-polygon self-intersection detection
+// ---------------------------------------------------------------------------
+
+// Two edges are adjacent when they share a vertex: consecutive indices, or the
+// wraparound pair of the last edge with the first. Those always "touch" by
+// construction and must not be reported as crossings.
+fn edges_adjacent(a: usize, b: usize, edge_count: usize) -> bool {
+    let (lo, hi) = if a < b { (a, b) } else { (b, a) };
+    hi - lo == 1 || (lo == 0 && hi == edge_count - 1)
+}
+
+// Report every point where two non-adjacent edges of a single polygon cross —
+// a "knot" in geomusica's morphing shapes. Adjacent and wraparound edge pairs
+// are skipped. Dense polygons route through the x-sorted active-interval scan
+// to skip most far-apart pairs; small ones use a direct AABB-filtered scan.
+// Both branches compute crossings with the same robust predicate.
+#[wasm_bindgen]
+pub fn find_self_intersections(vertices: &[f64]) -> Vec<f64> {
+    let edge_count = vertices.len() / 2;
+    if edge_count < 4 {
+        return Vec::new();
+    }
+
+    if edge_count > ACTIVE_SCAN_THRESHOLD {
+        let mut segments = Vec::new();
+        collect_segments(vertices, 0, &mut segments);
+        // Segment index equals edge index, so adjacency filters by index. Use
+        // the robust predicate here too, matching the small-polygon branch.
+        return active_interval_crossings(&segments, &|a, b| {
+            if edges_adjacent(a, b, edge_count) {
+                return None;
+            }
+            let sa = &segments[a];
+            let sb = &segments[b];
+            match robust_intersection(
+                &Point::new(sa.ax, sa.ay),
+                &Point::new(sa.bx, sa.by),
+                &Point::new(sb.ax, sb.ay),
+                &Point::new(sb.bx, sb.by),
+            ) {
+                SegmentIntersection::Proper(p) => Some(p),
+                _ => None,
+            }
+        });
+    }
+
+    // Direct scan with the robust predicate and an AABB broad-phase reject.
+    let boxes = edge_boxes(vertices);
+    let mut intersections = Vec::new();
+    let n = vertices.len();
+    for i in 0..edge_count {
+        for j in (i + 1)..edge_count {
+            if edges_adjacent(i, j, edge_count) || boxes_disjoint(&boxes[i], &boxes[j]) {
+                continue;
+            }
+
+            let ei = i * 2;
+            let ej = j * 2;
+            let p1 = Point::new(vertices[ei], vertices[ei + 1]);
+            let p2 = Point::new(vertices[(ei + 2) % n], vertices[(ei + 3) % n]);
+            let p3 = Point::new(vertices[ej], vertices[ej + 1]);
+            let p4 = Point::new(vertices[(ej + 2) % n], vertices[(ej + 3) % n]);
+
+            if let SegmentIntersection::Proper(p) = robust_intersection(&p1, &p2, &p3, &p4) {
+                intersections.push(p.x);
+                intersections.push(p.y);
+            }
+        }
+    }
+
+    intersections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Allow a small slop on floating-point coordinate comparisons.
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    // Small deterministic LCG so the fuzz cases are reproducible without a
+    // `rand` dependency.
+    struct Lcg(u64);
+    impl Lcg {
+        fn next_f64(&mut self) -> f64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((self.0 >> 33) as f64) / ((1u64 << 31) as f64)
+        }
+    }
+
+    // A random convex (hence simple) CCW polygon: vertices at sorted angles
+    // around a center, so the active-scan and brute paths have well-defined
+    // crossings.
+    fn random_convex_polygon(rng: &mut Lcg) -> Vec<f64> {
+        let n = 3 + (rng.next_f64() * 9.0) as usize; // 3..=11 vertices
+        let cx = rng.next_f64() * 4.0;
+        let cy = rng.next_f64() * 4.0;
+        let r = 0.5 + rng.next_f64() * 2.0;
+        let mut angles: Vec<f64> = (0..n).map(|_| rng.next_f64() * std::f64::consts::TAU).collect();
+        angles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut verts = Vec::with_capacity(n * 2);
+        for a in angles {
+            verts.push(cx + r * a.cos());
+            verts.push(cy + r * a.sin());
+        }
+        verts
+    }
+
+    // A random simple but concave CCW polygon: a star with vertices at sorted
+    // angles and alternating inner/outer radii. The reflex vertices and the
+    // wider x-extent exercise the active list far harder than a convex blob.
+    fn random_concave_polygon(rng: &mut Lcg) -> Vec<f64> {
+        let spikes = 3 + (rng.next_f64() * 4.0) as usize; // 3..=6 spikes
+        let cx = rng.next_f64() * 4.0;
+        let cy = rng.next_f64() * 4.0;
+        let outer = 1.0 + rng.next_f64() * 2.0;
+        let inner = 0.3 + rng.next_f64() * 0.5;
+        let mut verts = Vec::with_capacity(spikes * 4);
+        for k in 0..(spikes * 2) {
+            let a = k as f64 / (spikes * 2) as f64 * std::f64::consts::TAU;
+            let r = if k % 2 == 0 { outer } else { inner };
+            verts.push(cx + r * a.cos());
+            verts.push(cy + r * a.sin());
+        }
+        verts
+    }
+
+    // Do two flat point arrays describe the same multiset of points?
+    fn same_point_set(a: &[f64], b: &[f64]) -> bool {
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut used = vec![false; b.len() / 2];
+        for pa in a.chunks_exact(2) {
+            let mut matched = false;
+            for (k, pb) in b.chunks_exact(2).enumerate() {
+                if !used[k] && approx(pa[0], pb[0]) && approx(pa[1], pb[1]) {
+                    used[k] = true;
+                    matched = true;
+                    break;
+                }
+            }
+            if !matched {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[test]
+    fn active_scan_matches_brute_force_fuzz() {
+        let mut rng = Lcg(0x9E3779B97F4A7C15);
+        for round in 0..2000 {
+            // Mix convex and concave shapes and keep their centers close, so
+            // the x-overlapping concave cases — where the active scan differs
+            // most from brute — are well covered, not just disjoint blobs.
+            let a = if round % 2 == 0 {
+                random_concave_polygon(&mut rng)
+            } else {
+                random_convex_polygon(&mut rng)
+            };
+            let b = if round % 3 == 0 {
+                random_concave_polygon(&mut rng)
+            } else {
+                random_convex_polygon(&mut rng)
+            };
+            let brute = find_all_intersections_brute(&a, &b);
+            let active = find_all_intersections_active(&a, &b);
+            assert!(
+                same_point_set(&brute, &active),
+                "active scan {:?} disagreed with brute {:?} for {:?} / {:?}",
+                active,
+                brute,
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn offset_square_inward() {
+        // A unit-step CCW square offset inward by 0.5 is the inner square.
+        let square = [0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 2.0];
+        let inner = offset_polygon(&square, -0.5);
+        let expected = [0.5, 0.5, 1.5, 0.5, 1.5, 1.5, 0.5, 1.5];
+        assert!(
+            same_point_set(&inner, &expected),
+            "inward offset was {:?}",
+            inner
+        );
+    }
+
+    #[test]
+    fn self_intersection_bowtie() {
+        // A figure-eight: edges 0 and 2 cross at (1, 1), the other non-adjacent
+        // pair is parallel.
+        let bowtie = [0.0, 0.0, 2.0, 2.0, 2.0, 0.0, 0.0, 2.0];
+        let hits = find_self_intersections(&bowtie);
+        assert!(same_point_set(&hits, &[1.0, 1.0]), "got {:?}", hits);
+    }
+
+    #[test]
+    fn self_intersection_large_path_matches_brute() {
+        // Reference scan over every non-adjacent edge pair using the same
+        // robust predicate the active-scan path emits with.
+        fn brute_self(v: &[f64]) -> Vec<f64> {
+            let n = v.len();
+            let edge_count = n / 2;
+            let mut out = Vec::new();
+            for i in 0..edge_count {
+                for j in (i + 1)..edge_count {
+                    if edges_adjacent(i, j, edge_count) {
+                        continue;
+                    }
+                    let (ei, ej) = (i * 2, j * 2);
+                    if let SegmentIntersection::Proper(p) = robust_intersection(
+                        &Point::new(v[ei], v[ei + 1]),
+                        &Point::new(v[(ei + 2) % n], v[(ei + 3) % n]),
+                        &Point::new(v[ej], v[ej + 1]),
+                        &Point::new(v[(ej + 2) % n], v[(ej + 3) % n]),
+                    ) {
+                        out.push(p.x);
+                        out.push(p.y);
+                    }
+                }
+            }
+            out
+        }
+
+        // 70 points on a circle (>ACTIVE_SCAN_THRESHOLD) visited in shuffled
+        // order, so the ring crosses itself many times and takes the active
+        // scan path. Distinct radii keep it in general position.
+        let mut rng = Lcg(0xD1B54A32D192ED03);
+        let n = 70usize;
+        let mut idx: Vec<usize> = (0..n).collect();
+        for k in (1..n).rev() {
+            let j = (rng.next_f64() * (k + 1) as f64) as usize;
+            idx.swap(k, j);
+        }
+        let mut v = Vec::with_capacity(n * 2);
+        for &orig in &idx {
+            let a = orig as f64 / n as f64 * std::f64::consts::TAU;
+            let r = 1.0 + 0.013 * orig as f64;
+            v.push(r * a.cos());
+            v.push(r * a.sin());
+        }
+
+        let active = find_self_intersections(&v);
+        assert!(n > ACTIVE_SCAN_THRESHOLD);
+        assert!(
+            same_point_set(&active, &brute_self(&v)),
+            "active-scan self-intersections disagreed with brute reference"
+        );
+    }
+
+    #[test]
+    fn wkt_round_trip() {
+        assert_eq!(point_to_wkt(&Point::new(1.5, 2.5)), "POINT (1.5 2.5)");
+
+        // Parsing drops the repeated closing vertex.
+        let parsed = parse_wkt_polygon("POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))");
+        assert_eq!(parsed, vec![0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 2.0]);
+
+        // Serializing closes the ring back up.
+        assert_eq!(
+            polygon_to_wkt(&parsed),
+            "POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))"
+        );
+    }
+
+    #[test]
+    fn meta_records_edges_and_params() {
+        let big = [0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 2.0];
+        let shifted = [1.0, 1.0, 3.0, 1.0, 3.0, 3.0, 1.0, 3.0];
+        let records = find_all_intersections_meta(&big, &shifted);
+        assert_eq!(records.len(), 2);
+
+        for r in &records {
+            // Both crossings fall at the midpoint of their edges.
+            assert!(approx(r.ua, 0.5) && approx(r.ub, 0.5));
+            // Edge index pairs and points line up: A-right x B-bottom at (2,1),
+            // A-top x B-left at (1,2).
+            match (r.i, r.j) {
+                (1, 0) => assert!(approx(r.x, 2.0) && approx(r.y, 1.0)),
+                (2, 3) => assert!(approx(r.x, 1.0) && approx(r.y, 2.0)),
+                other => panic!("unexpected edge pair {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn robust_predicate_proper_collinear_none() {
+        // Proper crossing of an X at the origin.
+        match robust_intersection(
+            &Point::new(-1.0, -1.0),
+            &Point::new(1.0, 1.0),
+            &Point::new(-1.0, 1.0),
+            &Point::new(1.0, -1.0),
+        ) {
+            SegmentIntersection::Proper(p) => {
+                assert!(approx(p.x, 0.0) && approx(p.y, 0.0));
+            }
+            _ => panic!("expected a proper crossing"),
+        }
+
+        // Collinear overlap along the x-axis: [0,2] and [1,3] share [1,2].
+        match robust_intersection(
+            &Point::new(0.0, 0.0),
+            &Point::new(2.0, 0.0),
+            &Point::new(1.0, 0.0),
+            &Point::new(3.0, 0.0),
+        ) {
+            SegmentIntersection::Collinear { start, end } => {
+                assert!(approx(start.x, 1.0) && approx(end.x, 2.0));
+            }
+            _ => panic!("expected a collinear overlap"),
+        }
+
+        // Parallel but not collinear: no intersection.
+        assert!(matches!(
+            robust_intersection(
+                &Point::new(0.0, 0.0),
+                &Point::new(2.0, 0.0),
+                &Point::new(0.0, 1.0),
+                &Point::new(2.0, 1.0),
+            ),
+            SegmentIntersection::None
+        ));
+    }
+
+    #[test]
+    fn broad_phase_disjoint_and_overlapping() {
+        // Far-apart squares: the overall-bbox reject returns no crossings.
+        let a = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        let far = [5.0, 5.0, 6.0, 5.0, 6.0, 6.0, 5.0, 6.0];
+        assert!(find_all_intersections(&a, &far).is_empty());
+
+        // Overlapping squares still report both true crossings.
+        let big = [0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 2.0];
+        let shifted = [1.0, 1.0, 3.0, 1.0, 3.0, 3.0, 1.0, 3.0];
+        let hits = find_all_intersections(&big, &shifted);
+        assert!(
+            same_point_set(&hits, &[2.0, 1.0, 1.0, 2.0]),
+            "got {:?}",
+            hits
+        );
+    }
+
+    #[test]
+    fn clip_intersection_of_overlapping_squares() {
+        // Two unit-step squares overlapping in the [1,1]-[2,2] box.
+        let a = [0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 2.0];
+        let b = [1.0, 1.0, 3.0, 1.0, 3.0, 3.0, 1.0, 3.0];
+        let area = intersection_area(&a, &b).abs();
+        assert!(approx(area, 1.0), "overlap area was {}", area);
+    }
+
+    #[test]
+    fn clip_overlap_is_single_contour() {
+        // The overlap of the two squares is one square contour of four points.
+        let a = [0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 2.0];
+        let b = [1.0, 1.0, 3.0, 1.0, 3.0, 3.0, 1.0, 3.0];
+        let result = polygon_intersection(&a, &b);
+        assert_eq!(result.offsets, vec![0, 4]);
+        assert!(same_point_set(
+            &result.coords,
+            &[1.0, 1.0, 2.0, 1.0, 2.0, 2.0, 1.0, 2.0]
+        ));
+    }
+
+    #[test]
+    fn union_of_disjoint_returns_both_rings() {
+        let a = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        let far = [5.0, 5.0, 6.0, 5.0, 6.0, 6.0, 5.0, 6.0];
+        let result = polygon_union(&a, &far);
+        // Both whole rings, subject first, each a four-point contour.
+        assert_eq!(result.offsets, vec![0, 4, 8]);
+        let mut expected = a.to_vec();
+        expected.extend_from_slice(&far);
+        assert_eq!(result.coords, expected);
+    }
+
+    #[test]
+    fn difference_of_disjoint_returns_subject() {
+        let a = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+        let far = [5.0, 5.0, 6.0, 5.0, 6.0, 6.0, 5.0, 6.0];
+        let result = polygon_difference(&a, &far);
+        assert_eq!(result.offsets, vec![0, 4]);
+        assert_eq!(result.coords, a.to_vec());
+    }
+
+    #[test]
+    fn intersection_of_nested_returns_inner_ring() {
+        let outer = [0.0, 0.0, 4.0, 0.0, 4.0, 4.0, 0.0, 4.0];
+        let inner = [1.0, 1.0, 3.0, 1.0, 3.0, 3.0, 1.0, 3.0];
+        let result = polygon_intersection(&outer, &inner);
+        assert_eq!(result.offsets, vec![0, 4]);
+        assert_eq!(result.coords, inner.to_vec());
+    }
+
+    #[test]
+    fn difference_of_nested_returns_subject_and_hole() {
+        let outer = [0.0, 0.0, 4.0, 0.0, 4.0, 4.0, 0.0, 4.0];
+        let inner = [1.0, 1.0, 3.0, 1.0, 3.0, 3.0, 1.0, 3.0];
+        let result = polygon_difference(&outer, &inner);
+        // Outer ring plus the inner ring as a hole: two contours.
+        assert_eq!(result.offsets, vec![0, 4, 8]);
+        let mut expected = outer.to_vec();
+        expected.extend_from_slice(&inner);
+        assert_eq!(result.coords, expected);
+    }
+}